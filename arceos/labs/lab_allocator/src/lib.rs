@@ -24,10 +24,40 @@ const POOL_SIZES: [usize; 8] = [
 // 优化3：使用类型别名提高代码可读性
 type PoolArray = [u8];
 
+// 优化13：每个块末尾额外写一个 footer（边界标记），复制一份 size；
+// 物理相邻的后继块由头部的 size 加上 footer 长度算出。footer 只写入不读取，
+// 前驱块是在按地址有序插入时遍历链表得到的（见 insert_sorted_and_coalesce）
+const FOOTER_SIZE: usize = mem::size_of::<usize>();
+
+// 优化11：支持伙伴系统（buddy allocator）作为可选的分配策略
+// order k 对应大小为 2^k 的块，MIN_ORDER/MAX_ORDER 划定可管理的块大小范围
+const MIN_ORDER: usize = 5; // 2^5 = 32 bytes，小于一个 Block 头部没有意义
+const MAX_ORDER: usize = 30; // 2^30 = 1GiB，超过这个数量级没有必要再合并
+const ORDER_COUNT: usize = MAX_ORDER - MIN_ORDER + 1;
+
+// 优化15：支持 bitmap/genalloc 风格的区域分配器作为第三种策略
+// 每个 bit 代表一个 `1 << bitmap_min_order` 大小的 chunk，没有任何 per-block 头部开销
+// bitmap 数组大小固定，决定了这种模式下能管理的最大 chunk 数
+const BITMAP_WORDS: usize = 4096;
+const BITMAP_MAX_CHUNKS: usize = BITMAP_WORDS * 32;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum Strategy {
+    BestFit,
+    Buddy,
+    Bitmap,
+}
+
+// 优化17：free 链表要把 *mut u8 指针直接写进空闲块的头几个字节（见 init_pool_slab），
+// 裸的 [u8; N] 静态数组只有 1 字节对齐，在严格对齐的目标（如未开启非对齐访问的
+// RISC-V/AArch64）上做指针宽度的写入是未定义行为；用这个 wrapper 把对齐提到 usize
+#[repr(align(8))]
+struct AlignedPool<const N: usize>([u8; N]);
+
 // 优化4：静态内存池使用宏定义，减少代码重复
 macro_rules! define_memory_pools {
     ($($name:ident: $size:expr),*) => {
-        $(static mut $name: [u8; $size + MAX_INDICATOR] = [0; $size + MAX_INDICATOR];)*
+        $(static mut $name: AlignedPool<{ $size + MAX_INDICATOR }> = AlignedPool([0; $size + MAX_INDICATOR]);)*
     }
 }
 
@@ -43,11 +73,16 @@ define_memory_pools! {
 }
 
 // 优化5：添加内存池管理结构
+// 优化12：每个规格不再只发放一次，而是变成真正的 slab 缓存——
+// 把底层静态数组切成定长块，free 链表直接穿过空闲块本身（块的头部 size_of::<*mut u8>() 字节存 next 指针）
 #[derive(Debug)]
 struct PoolInfo {
     base: *mut u8,
-    size: usize,
-    used: bool,
+    block_size: usize,
+    capacity: usize,
+    free_list: *mut u8,
+    free_count: usize,
+    initialized: bool,
 }
 
 pub struct LabByteAllocator {
@@ -58,6 +93,14 @@ pub struct LabByteAllocator {
     // 优化6：添加内存池追踪
     pools: [PoolInfo; 8],
     allocation_count: usize,
+    // 优化11：当前使用的分配策略，以及伙伴系统各 order 的空闲链表
+    strategy: Strategy,
+    buddy_free: [*mut Block; ORDER_COUNT],
+    // 优化15：bitmap 区域分配器的位图及粒度配置
+    bitmap: [u32; BITMAP_WORDS],
+    bitmap_min_order: usize,
+    bitmap_base: usize,
+    bitmap_chunks: usize,
 }
 
 unsafe impl Send for LabByteAllocator {}
@@ -72,10 +115,71 @@ impl LabByteAllocator {
             free_list: null_mut(),
             pools: [PoolInfo {
                 base: null_mut(),
-                size: 0,
-                used: false
+                block_size: 0,
+                capacity: 0,
+                free_list: null_mut(),
+                free_count: 0,
+                initialized: false,
+            }; 8],
+            allocation_count: 0,
+            strategy: Strategy::BestFit,
+            buddy_free: [null_mut(); ORDER_COUNT],
+            bitmap: [0; BITMAP_WORDS],
+            bitmap_min_order: MIN_ORDER,
+            bitmap_base: 0,
+            bitmap_chunks: 0,
+        }
+    }
+
+    /// 创建一个使用伙伴系统（而非默认的 best-fit 空闲链表）管理内存的分配器。
+    /// 策略在 `init` 之前选定，`init`/`add_memory` 会据此构建伙伴系统的空闲链表数组。
+    pub const fn new_buddy() -> Self {
+        Self {
+            start: 0,
+            total_size: 0,
+            used_size: 0,
+            free_list: null_mut(),
+            pools: [PoolInfo {
+                base: null_mut(),
+                block_size: 0,
+                capacity: 0,
+                free_list: null_mut(),
+                free_count: 0,
+                initialized: false,
+            }; 8],
+            allocation_count: 0,
+            strategy: Strategy::Buddy,
+            buddy_free: [null_mut(); ORDER_COUNT],
+            bitmap: [0; BITMAP_WORDS],
+            bitmap_min_order: MIN_ORDER,
+            bitmap_base: 0,
+            bitmap_chunks: 0,
+        }
+    }
+
+    /// 创建一个 bitmap/genalloc 风格的区域分配器，每个 bit 管理 `1 << min_order` 字节的 chunk。
+    /// `min_order` 同样在 `init` 之前选定。
+    pub const fn new_bitmap(min_order: usize) -> Self {
+        Self {
+            start: 0,
+            total_size: 0,
+            used_size: 0,
+            free_list: null_mut(),
+            pools: [PoolInfo {
+                base: null_mut(),
+                block_size: 0,
+                capacity: 0,
+                free_list: null_mut(),
+                free_count: 0,
+                initialized: false,
             }; 8],
             allocation_count: 0,
+            strategy: Strategy::Bitmap,
+            buddy_free: [null_mut(); ORDER_COUNT],
+            bitmap: [0; BITMAP_WORDS],
+            bitmap_min_order: min_order,
+            bitmap_base: 0,
+            bitmap_chunks: 0,
         }
     }
 
@@ -107,36 +211,500 @@ impl LabByteAllocator {
         })
     }
 
-    // 优化8：改进内存池分配策略
+    /// 规格 `index` 对应的静态池子基址，不经过 `initialized` 标记、也不借出引用，
+    /// 只用来在挂上 slab 之前判断这块内存天然满足多大的对齐。
+    unsafe fn pool_static_addr(index: usize) -> usize {
+        match index {
+            0 => core::ptr::addr_of!(POOL_32) as usize,
+            1 => core::ptr::addr_of!(POOL_128) as usize,
+            2 => core::ptr::addr_of!(POOL_512) as usize,
+            3 => core::ptr::addr_of!(POOL_2048) as usize,
+            4 => core::ptr::addr_of!(POOL_8_1024) as usize,
+            5 => core::ptr::addr_of!(POOL_32_1024) as usize,
+            6 => core::ptr::addr_of!(POOL_128_1024) as usize,
+            7 => core::ptr::addr_of!(POOL_512_1024) as usize,
+            _ => 0,
+        }
+    }
+
+    // 优化8/12：改进内存池分配策略——惰性初始化 slab，之后 O(1) 摘取/归还空闲块
     unsafe fn allocate_from_pool(&mut self, layout: Layout) -> Option<NonNull<u8>> {
-        if let Some(index) = POOL_SIZES.iter()
-            .position(|&size| size >= layout.size() && size >= layout.align())
-        {
-            if !self.pools[index].used {
-                let pool = match index {
-                    0 => &mut POOL_32,
-                    1 => &mut POOL_128,
-                    2 => &mut POOL_512,
-                    3 => &mut POOL_2048,
-                    4 => &mut POOL_8_1024,
-                    5 => &mut POOL_32_1024,
-                    6 => &mut POOL_128_1024,
-                    7 => &mut POOL_512_1024,
-                    _ => return None,
-                };
-                self.pools[index].used = true;
-                self.pools[index].base = pool.as_mut_ptr();
-                self.pools[index].size = POOL_SIZES[index];
-                return NonNull::new(pool.as_mut_ptr());
-            }
+        let index = POOL_SIZES.iter()
+            .position(|&size| size >= layout.size() && size >= layout.align())?;
+
+        // 优化18：AlignedPool 只保证 8 字节对齐，请求的 align 一旦超过这个限度，
+        // 池子基址未必满足——退回最佳适应空闲链表，而不是悄悄发出未对齐的指针
+        if Self::pool_static_addr(index) % layout.align() != 0 {
+            return None;
         }
-        None
+
+        if !self.pools[index].initialized {
+            let pool: &mut [u8] = match index {
+                0 => &mut POOL_32.0,
+                1 => &mut POOL_128.0,
+                2 => &mut POOL_512.0,
+                3 => &mut POOL_2048.0,
+                4 => &mut POOL_8_1024.0,
+                5 => &mut POOL_32_1024.0,
+                6 => &mut POOL_128_1024.0,
+                7 => &mut POOL_512_1024.0,
+                _ => return None,
+            };
+            self.init_pool_slab(index, pool);
+        }
+
+        self.pool_alloc(index)
+    }
+
+    /// 把 `pool` 切成 `POOL_SIZES[index]` 大小的定长块，并把 next 指针串进每个空闲块的头部。
+    unsafe fn init_pool_slab(&mut self, index: usize, pool: &mut [u8]) {
+        let block_size = POOL_SIZES[index];
+        let capacity = pool.len() / block_size;
+        let base = pool.as_mut_ptr();
+
+        let mut head: *mut u8 = null_mut();
+        for i in (0..capacity).rev() {
+            let block = base.add(i * block_size);
+            (block as *mut *mut u8).write(head);
+            head = block;
+        }
+
+        self.pools[index] = PoolInfo {
+            base,
+            block_size,
+            capacity,
+            free_list: head,
+            free_count: capacity,
+            initialized: true,
+        };
+        self.total_size += capacity * block_size;
+    }
+
+    /// 从规格 `index` 的空闲链表头部摘取一个块，O(1)。
+    unsafe fn pool_alloc(&mut self, index: usize) -> Option<NonNull<u8>> {
+        let pool = &mut self.pools[index];
+        let block = pool.free_list;
+        if block.is_null() {
+            return None;
+        }
+        pool.free_list = *(block as *mut *mut u8);
+        pool.free_count -= 1;
+        self.allocation_count += 1;
+        NonNull::new(block)
     }
 
     // 优化9：添加内存对齐处理
     fn align_up(size: usize, align: usize) -> usize {
         (size + align - 1) & !(align - 1)
     }
+
+    // 优化11：伙伴系统相关的辅助方法
+
+    /// 把一段 `[start, start + size)` 的内存按尽可能大的 2 的幂依次切好挂到对应的空闲链表上。
+    unsafe fn buddy_add_region(&mut self, start: usize, size: usize) {
+        let mut addr = start;
+        let mut remaining = size;
+        while remaining >= (1 << MIN_ORDER) {
+            let mut order = MAX_ORDER.min(usize::BITS as usize - 1 - remaining.leading_zeros() as usize);
+            // 块不能跨越未对齐的地址，也不能超过剩余大小
+            while order > MIN_ORDER && (addr & ((1 << order) - 1) != 0 || (1 << order) > remaining) {
+                order -= 1;
+            }
+            self.buddy_push(order, addr as *mut Block);
+            addr += 1 << order;
+            remaining -= 1 << order;
+        }
+    }
+
+    /// 把一个 order 对应大小的块压入该 order 的空闲链表头部。
+    unsafe fn buddy_push(&mut self, order: usize, block: *mut Block) {
+        let idx = order - MIN_ORDER;
+        (*block).size = 1 << order;
+        (*block).next = self.buddy_free[idx];
+        self.buddy_free[idx] = block;
+    }
+
+    /// 从指定 order 的空闲链表中摘除地址为 `addr` 的块（用于伙伴合并），找到返回 true。
+    unsafe fn buddy_remove(&mut self, order: usize, addr: *mut Block) -> bool {
+        let idx = order - MIN_ORDER;
+        let mut prev = &mut self.buddy_free[idx] as *mut *mut Block;
+        let mut current = self.buddy_free[idx];
+        while !current.is_null() {
+            if current == addr {
+                *prev = (*current).next;
+                return true;
+            }
+            prev = &mut (*current).next;
+            current = *prev;
+        }
+        false
+    }
+
+    /// 为请求分配的大小（已含 `Block` 头部）找到最小满足要求的 order。
+    fn order_for_size(size: usize) -> usize {
+        let mut order = MIN_ORDER;
+        while order < MAX_ORDER && (1usize << order) < size {
+            order += 1;
+        }
+        order
+    }
+
+    /// 从 `order` 开始向上找到第一个非空的空闲链表，逐级拆分直到得到一个 order 大小的块。
+    unsafe fn buddy_find(&mut self, order: usize) -> Option<*mut Block> {
+        let mut found_order = order;
+        while found_order <= MAX_ORDER && self.buddy_free[found_order - MIN_ORDER].is_null() {
+            found_order += 1;
+        }
+        if found_order > MAX_ORDER {
+            return None;
+        }
+
+        let idx = found_order - MIN_ORDER;
+        let block = self.buddy_free[idx];
+        self.buddy_free[idx] = (*block).next;
+
+        // 从 found_order 逐级往下拆分到目标 order，每次把多出来的一半伙伴挂回空闲链表
+        let mut addr = block as usize;
+        let mut cur_order = found_order;
+        while cur_order > order {
+            cur_order -= 1;
+            let buddy_addr = addr + (1 << cur_order);
+            self.buddy_push(cur_order, buddy_addr as *mut Block);
+        }
+
+        let result = addr as *mut Block;
+        (*result).size = 1 << order;
+        Some(result)
+    }
+
+    /// 块头部到返回给调用者的指针之间要留出的字节数：至少放得下 `Block`，
+    /// 同时向上取整到 `align`，这样块本身天然的 order 对齐（2 的幂）就蕴含了
+    /// `block + header_size` 也是 `align` 对齐的，不需要再对指针单独 align_up。
+    fn buddy_header_size(align: usize) -> usize {
+        Self::align_up(mem::size_of::<Block>(), align.max(1))
+    }
+
+    unsafe fn buddy_alloc(&mut self, layout: Layout) -> AllocResult<NonNull<u8>> {
+        let align = layout.align().max(1);
+        let requested = Self::align_up(layout.size().max(1), align);
+        let header = Self::buddy_header_size(align);
+        let needed = requested + header;
+        let order = Self::order_for_size(needed);
+
+        match self.buddy_find(order) {
+            Some(block) => {
+                let ptr = block as usize + header;
+                self.used_size += 1 << order;
+                self.allocation_count += 1;
+                Ok(NonNull::new_unchecked(ptr as *mut u8))
+            }
+            None => Err(allocator::AllocError::NoMemory),
+        }
+    }
+
+    unsafe fn buddy_dealloc(&mut self, ptr: NonNull<u8>, layout: Layout) {
+        let header = Self::buddy_header_size(layout.align().max(1));
+        let block = (ptr.as_ptr() as usize - header) as *mut Block;
+        let mut order = (*block).size.trailing_zeros() as usize;
+        let mut addr = block as usize;
+
+        self.used_size -= (*block).size;
+        self.allocation_count -= 1;
+
+        // 不断尝试和伙伴合并，直到伙伴不是空闲的或者已经到达最大 order
+        while order < MAX_ORDER {
+            let buddy_addr = self.start + ((addr - self.start) ^ (1 << order));
+            if self.buddy_remove(order, buddy_addr as *mut Block) {
+                addr = addr.min(buddy_addr);
+                order += 1;
+            } else {
+                break;
+            }
+        }
+
+        self.buddy_push(order, addr as *mut Block);
+    }
+
+    // 优化13：realloc 及其依赖的边界标记（footer）辅助方法
+
+    /// 在块末尾写入 footer，使其复制一份 `size`。
+    unsafe fn write_footer(block: *mut Block, size: usize) {
+        let footer = (block as usize + mem::size_of::<Block>() + size) as *mut usize;
+        footer.write(size);
+    }
+
+    /// 根据头部记录的 `size` 跳到物理相邻的后继块地址。
+    unsafe fn successor_of(block: *mut Block) -> *mut Block {
+        (block as usize + mem::size_of::<Block>() + (*block).size + FOOTER_SIZE) as *mut Block
+    }
+
+    /// 从 best-fit 空闲链表中摘除地址恰为 `target` 的块，找到返回 true。
+    unsafe fn take_from_free_list(&mut self, target: *mut Block) -> bool {
+        let mut prev = &mut self.free_list as *mut *mut Block;
+        let mut current = self.free_list;
+        while !current.is_null() {
+            if current == target {
+                *prev = (*current).next;
+                return true;
+            }
+            prev = &mut (*current).next;
+            current = *prev;
+        }
+        false
+    }
+
+    /// 收缩场景：把多出来的尾部切成一个新的空闲块挂回链表，留下的空间不够时原地不动。
+    unsafe fn shrink_in_place(&mut self, ptr: NonNull<u8>, old_size: usize, new_size: usize) -> NonNull<u8> {
+        let block = (ptr.as_ptr() as *mut Block).sub(1);
+        let leftover = old_size - new_size;
+
+        // 无论是否够拆出一个可复用的空闲块，缩小的字节数都已经不再被占用了，
+        // 所以记账要无条件执行；只有"要不要物理拆分出一个新的空闲块"才看阈值。
+        (*block).size = new_size;
+        Self::write_footer(block, new_size);
+        self.used_size -= leftover;
+
+        if leftover > mem::size_of::<Block>() + FOOTER_SIZE {
+            let tail_size = leftover - mem::size_of::<Block>() - FOOTER_SIZE;
+            let tail = Self::successor_of(block);
+            (*tail).size = tail_size;
+            Self::write_footer(tail, tail_size);
+            self.insert_sorted_and_coalesce(tail);
+        }
+
+        ptr
+    }
+
+    /// 支持原地扩容 + 边界标记合并的 realloc：优先吸收物理相邻且空闲的后继块，
+    /// 吸收不了时退化为「新分配 + 拷贝 + 释放旧块」。
+    ///
+    /// `Block`/footer 的原地扩缩只对 best-fit 策略有意义——伙伴系统的块头复用同一个
+    /// `size` 字段编码 order，bitmap 模式在分配地址前面根本没有头部，两者都不能直接
+    /// 套用这里的指针运算，所以统一退化为新分配 + 拷贝 + 释放。best-fit 模式下从
+    /// slab 池子发出的块同样没有 `Block` 头部，也走这条退化路径。
+    pub unsafe fn realloc(
+        &mut self,
+        ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> AllocResult<NonNull<u8>> {
+        let from_pool = self.pools.iter().any(|pool| {
+            pool.initialized
+                && ptr.as_ptr() >= pool.base
+                && ptr.as_ptr() < pool.base.add(pool.capacity * pool.block_size)
+        });
+        if self.strategy != Strategy::BestFit || from_pool {
+            let new_ptr = self.alloc(new_layout)?;
+            let copy_size = old_layout.size().min(new_layout.size());
+            core::ptr::copy_nonoverlapping(ptr.as_ptr(), new_ptr.as_ptr(), copy_size);
+            self.dealloc(ptr, old_layout);
+            return Ok(new_ptr);
+        }
+
+        let old_size = Self::align_up(old_layout.size(), old_layout.align());
+        let new_size = Self::align_up(new_layout.size(), new_layout.align());
+
+        if new_size <= old_size {
+            return Ok(self.shrink_in_place(ptr, old_size, new_size));
+        }
+
+        let block = (ptr.as_ptr() as *mut Block).sub(1);
+        let extra = new_size - old_size;
+        let next = Self::successor_of(block);
+
+        if (next as usize) + mem::size_of::<Block>() <= self.start + self.total_size
+            && self.take_from_free_list(next)
+        {
+            let available = mem::size_of::<Block>() + FOOTER_SIZE + (*next).size;
+            if available >= extra {
+                let remainder = available - extra;
+                (*block).size += extra;
+
+                if remainder >= mem::size_of::<Block>() + FOOTER_SIZE {
+                    let tail_size = remainder - mem::size_of::<Block>() - FOOTER_SIZE;
+                    let tail = Self::successor_of(block);
+                    (*tail).size = tail_size;
+                    Self::write_footer(tail, tail_size);
+                    self.insert_sorted_and_coalesce(tail);
+                } else {
+                    (*block).size += remainder;
+                }
+
+                Self::write_footer(block, (*block).size);
+                self.used_size += extra;
+                return Ok(ptr);
+            }
+
+            // 合并后还是不够大，放回空闲链表，走下面的回退路径
+            self.insert_sorted_and_coalesce(next);
+        }
+
+        // 回退：新分配 + 拷贝旧数据 + 释放旧块
+        let new_ptr = self.alloc(new_layout)?;
+        core::ptr::copy_nonoverlapping(ptr.as_ptr(), new_ptr.as_ptr(), old_size);
+        self.dealloc(ptr, old_layout);
+        Ok(new_ptr)
+    }
+
+    // 优化14：free_list 按地址升序维护，插入时遍历链表找到有序位置（O(n)），
+    // 随后顺带和前驱/后继做合并，这一步只比较地址，是 O(1) 的
+    unsafe fn insert_sorted_and_coalesce(&mut self, block: *mut Block) {
+        let mut prev_node: *mut Block = null_mut();
+        let mut current = self.free_list;
+        while !current.is_null() && (current as usize) < (block as usize) {
+            prev_node = current;
+            current = (*current).next;
+        }
+
+        (*block).next = current;
+        if prev_node.is_null() {
+            self.free_list = block;
+        } else {
+            (*prev_node).next = block;
+        }
+
+        // 向后合并：block 和紧随其后的空闲块物理相邻
+        if !current.is_null() && Self::successor_of(block) == current {
+            (*block).size += mem::size_of::<Block>() + FOOTER_SIZE + (*current).size;
+            (*block).next = (*current).next;
+            Self::write_footer(block, (*block).size);
+        }
+
+        // 向前合并：前驱就是上面有序遍历时得到的 prev_node，只需比较地址，不读取 footer
+        if !prev_node.is_null() && Self::successor_of(prev_node) == block {
+            (*prev_node).size += mem::size_of::<Block>() + FOOTER_SIZE + (*block).size;
+            (*prev_node).next = (*block).next;
+            Self::write_footer(prev_node, (*prev_node).size);
+        }
+    }
+
+    // 优化15：bitmap 区域分配器的辅助方法
+
+    /// 把 `[start, start + size)` 这段区域追加进位图（按 `bitmap_min_order` 取整到整数个 chunk）。
+    ///
+    /// 第一次调用时把 base 本身向上取整到 chunk 大小的整数倍（丢弃开头不足一个 chunk 的
+    /// 余量），这样 `bitmap_base + i * chunk_size` 总是 chunk 对齐的地址，`bitmap_alloc`
+    /// 只需要在 chunk 下标空间里检查对齐，不用再对 base 取模。
+    fn bitmap_add_region(&mut self, start: usize, size: usize) {
+        let chunk_size = 1usize << self.bitmap_min_order;
+
+        if self.bitmap_chunks == 0 {
+            let aligned_base = Self::align_up(start, chunk_size);
+            let lost = aligned_base - start;
+            self.bitmap_base = aligned_base;
+            self.bitmap_chunks = (size.saturating_sub(lost) / chunk_size).min(BITMAP_MAX_CHUNKS);
+        } else {
+            let new_chunks = size / chunk_size;
+            self.bitmap_chunks = (self.bitmap_chunks + new_chunks).min(BITMAP_MAX_CHUNKS);
+        }
+    }
+
+    /// 把 `value` 向上取整成对 `align_chunks` 取余等于 `residue` 的最小值（`align_chunks` 为 2 的幂）。
+    fn align_chunk_index(value: usize, align_chunks: usize, residue: usize) -> usize {
+        if align_chunks <= 1 {
+            return value;
+        }
+        let rem = value % align_chunks;
+        if rem <= residue {
+            value - rem + residue
+        } else {
+            value - rem + align_chunks + residue
+        }
+    }
+
+    /// 在位图里找到第一段长度 >= `len` 个 chunk、且起始 chunk 满足 `layout` 对齐要求的连续空闲区间。
+    /// 每个字先用 `trailing_zeros`/`leading_zeros` 判断是否整体空闲/整体占用，快速跳过，
+    /// 其余情况才退化为逐位扫描；每扩展一段连续空闲区间就检查其中是否存在满足对齐的起点。
+    fn bitmap_first_fit(&self, len: usize, align_chunks: usize, residue: usize) -> Option<usize> {
+        let mut run = 0usize;
+        let mut run_start = 0usize;
+        let mut idx = 0usize;
+
+        while idx < self.bitmap_chunks {
+            let word = self.bitmap[idx / 32];
+
+            if idx % 32 == 0 && word.trailing_zeros() == 32 {
+                // 快速路径：整个字都空闲，trailing_zeros(word) == 32
+                let take = 32.min(self.bitmap_chunks - idx);
+                if run == 0 {
+                    run_start = idx;
+                }
+                run += take;
+                idx += take;
+            } else if idx % 32 == 0 && (!word).leading_zeros() == 32 {
+                // 快速路径：整个字都被占用，leading_zeros(!word) == 32
+                run = 0;
+                idx += 32;
+            } else {
+                // 慢速路径：逐位判断
+                let bit = idx % 32;
+                if word & (1 << bit) == 0 {
+                    if run == 0 {
+                        run_start = idx;
+                    }
+                    run += 1;
+                } else {
+                    run = 0;
+                }
+                idx += 1;
+            }
+
+            if run > 0 {
+                let aligned_start = Self::align_chunk_index(run_start, align_chunks, residue);
+                if aligned_start + len <= run_start + run {
+                    return Some(aligned_start);
+                }
+            }
+        }
+        None
+    }
+
+    fn bitmap_set_range(&mut self, start: usize, len: usize) {
+        for idx in start..start + len {
+            self.bitmap[idx / 32] |= 1 << (idx % 32);
+        }
+    }
+
+    fn bitmap_clear_range(&mut self, start: usize, len: usize) {
+        for idx in start..start + len {
+            self.bitmap[idx / 32] &= !(1 << (idx % 32));
+        }
+    }
+
+    fn bitmap_alloc(&mut self, layout: Layout) -> AllocResult<NonNull<u8>> {
+        let chunk_size = 1usize << self.bitmap_min_order;
+        let requested = Self::align_up(layout.size().max(1), layout.align().max(1));
+        let len = Self::align_up(requested, chunk_size) / chunk_size;
+
+        // layout.align() 可能比 chunk_size 更严格（例如页对齐的 DMA 缓冲区）；
+        // bitmap_base 已经是 chunk 对齐的，所以只需要在 chunk 下标空间里换算出
+        // 对应的对齐粒度和余数，first-fit 就能直接返回满足对齐的起点
+        let align_chunks = (layout.align() / chunk_size).max(1);
+        let base_chunks = self.bitmap_base / chunk_size;
+        let residue = (align_chunks - base_chunks % align_chunks) % align_chunks;
+
+        match self.bitmap_first_fit(len, align_chunks, residue) {
+            Some(start) => {
+                self.bitmap_set_range(start, len);
+                self.used_size += len * chunk_size;
+                self.allocation_count += 1;
+                let addr = self.bitmap_base + start * chunk_size;
+                Ok(unsafe { NonNull::new_unchecked(addr as *mut u8) })
+            }
+            None => Err(allocator::AllocError::NoMemory),
+        }
+    }
+
+    fn bitmap_dealloc(&mut self, ptr: NonNull<u8>, layout: Layout) {
+        let chunk_size = 1usize << self.bitmap_min_order;
+        let requested = Self::align_up(layout.size().max(1), layout.align().max(1));
+        let len = Self::align_up(requested, chunk_size) / chunk_size;
+
+        let start = (ptr.as_ptr() as usize - self.bitmap_base) / chunk_size;
+        self.bitmap_clear_range(start, len);
+        self.used_size -= len * chunk_size;
+        self.allocation_count -= 1;
+    }
 }
 
 impl BaseAllocator for LabByteAllocator {
@@ -144,13 +712,23 @@ impl BaseAllocator for LabByteAllocator {
         unsafe {
             let aligned_start = Self::align_up(start, mem::align_of::<Block>());
             let aligned_size = size - (aligned_start - start);
-            
+
             self.start = aligned_start;
             self.total_size = aligned_size;
-            
+
+            if self.strategy == Strategy::Buddy {
+                self.buddy_add_region(aligned_start, aligned_size);
+                return;
+            }
+            if self.strategy == Strategy::Bitmap {
+                self.bitmap_add_region(aligned_start, aligned_size);
+                return;
+            }
+
             let initial_block = aligned_start as *mut Block;
-            (*initial_block).size = aligned_size - mem::size_of::<Block>();
+            (*initial_block).size = aligned_size - mem::size_of::<Block>() - FOOTER_SIZE;
             (*initial_block).next = null_mut();
+            Self::write_footer(initial_block, (*initial_block).size);
             self.free_list = initial_block;
         }
     }
@@ -159,14 +737,42 @@ impl BaseAllocator for LabByteAllocator {
         unsafe {
             let aligned_start = Self::align_up(start, mem::align_of::<Block>());
             let aligned_size = size - (aligned_start - start);
-            
-            let new_block = aligned_start as *mut Block;
-            (*new_block).size = aligned_size - mem::size_of::<Block>();
-            (*new_block).next = self.free_list;
-            self.free_list = new_block;
-            
+
+            // 优化16：伙伴系统的地址换算只认一个 self.start 作为基址，
+            // 要求新区域紧跟在已管理区域之后，不支持悄悄拼接两段不相邻的物理内存
+            let prior_total = self.total_size;
             self.total_size += aligned_size;
-            self.merge_blocks();
+
+            if self.strategy == Strategy::Buddy {
+                let expected_contiguous_start = self.start + prior_total;
+                if aligned_start != expected_contiguous_start {
+                    self.total_size = prior_total;
+                    return Err(allocator::AllocError::InvalidParam);
+                }
+                self.buddy_add_region(aligned_start, aligned_size);
+                return Ok(());
+            }
+            if self.strategy == Strategy::Bitmap {
+                // 优化17：bitmap 只有一个 bitmap_base，后续区域的 chunk 下标都是
+                // 相对它算出来的，所以同样要求新区域紧跟在已管理区域之后，
+                // 否则 bitmap_alloc 会把缺口/越界地址当成合法分配发出去
+                if self.bitmap_chunks > 0 {
+                    let chunk_size = 1usize << self.bitmap_min_order;
+                    let expected_contiguous_start =
+                        self.bitmap_base + self.bitmap_chunks * chunk_size;
+                    if aligned_start != expected_contiguous_start {
+                        self.total_size = prior_total;
+                        return Err(allocator::AllocError::InvalidParam);
+                    }
+                }
+                self.bitmap_add_region(aligned_start, aligned_size);
+                return Ok(());
+            }
+
+            let new_block = aligned_start as *mut Block;
+            (*new_block).size = aligned_size - mem::size_of::<Block>() - FOOTER_SIZE;
+            Self::write_footer(new_block, (*new_block).size);
+            self.insert_sorted_and_coalesce(new_block);
         }
         Ok(())
     }
@@ -175,6 +781,15 @@ impl BaseAllocator for LabByteAllocator {
 impl ByteAllocator for LabByteAllocator {
     fn alloc(&mut self, layout: Layout) -> AllocResult<NonNull<u8>> {
         unsafe {
+            // 优化11：伙伴系统模式下直接走伙伴分配路径
+            if self.strategy == Strategy::Buddy {
+                return self.buddy_alloc(layout);
+            }
+            // 优化15：bitmap 模式下直接走位图分配路径
+            if self.strategy == Strategy::Bitmap {
+                return self.bitmap_alloc(layout);
+            }
+
             // 优化10：优先使用内存池
             if let Some(ptr) = self.allocate_from_pool(layout) {
                 return Ok(ptr);
@@ -199,22 +814,35 @@ impl ByteAllocator for LabByteAllocator {
 
     fn dealloc(&mut self, ptr: NonNull<u8>, layout: Layout) {
         unsafe {
-            // 检查是否是内存池分配的内存
-            if self.pools.iter().any(|pool| {
-                ptr.as_ptr() >= pool.base && 
-                ptr.as_ptr() < pool.base.add(pool.size)
+            // 优化11：伙伴系统模式下直接走伙伴回收路径
+            if self.strategy == Strategy::Buddy {
+                self.buddy_dealloc(ptr, layout);
+                return;
+            }
+            // 优化15：bitmap 模式下直接走位图回收路径
+            if self.strategy == Strategy::Bitmap {
+                self.bitmap_dealloc(ptr, layout);
+                return;
+            }
+
+            // 检查是否是内存池分配的内存：属于某个 slab 的地址范围就把块还给它的空闲链表
+            if let Some(pool) = self.pools.iter_mut().find(|pool| {
+                pool.initialized
+                    && ptr.as_ptr() >= pool.base
+                    && ptr.as_ptr() < pool.base.add(pool.capacity * pool.block_size)
             }) {
+                (ptr.as_ptr() as *mut *mut u8).write(pool.free_list);
+                pool.free_list = ptr.as_ptr();
+                pool.free_count += 1;
+                self.allocation_count -= 1;
                 return;
             }
 
             let block = (ptr.as_ptr() as *mut Block).sub(1);
-            (*block).next = self.free_list;
-            self.free_list = block;
-            
-            self.used_size -= layout.size();
+            self.used_size -= Self::align_up(layout.size(), layout.align());
             self.allocation_count -= 1;
-            
-            self.merge_blocks();
+
+            self.insert_sorted_and_coalesce(block);
         }
     }
 
@@ -223,10 +851,15 @@ impl ByteAllocator for LabByteAllocator {
     }
 
     fn used_bytes(&self) -> usize {
-        self.used_size
+        // 优化12：slab 缓存里正在使用的块也要计入已用字节数
+        let pool_used: usize = self.pools.iter()
+            .filter(|pool| pool.initialized)
+            .map(|pool| (pool.capacity - pool.free_count) * pool.block_size)
+            .sum();
+        self.used_size + pool_used
     }
 
     fn available_bytes(&self) -> usize {
-        self.total_size - self.used_size
+        self.total_size - self.used_bytes()
     }
 }